@@ -8,6 +8,39 @@ pub struct Registers {
     pc: u16,
 }
 
+/// A snapshot of the full state of a `Registers`, suitable for save states.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisterState {
+    af: u16,
+    bc: u16,
+    de: u16,
+    hl: u16,
+    sp: u16,
+    pc: u16,
+}
+
+/// 8-bit registers, addressable individually.
+pub enum Reg8 {
+    A,
+    B,
+    C,
+    D,
+    E,
+    H,
+    L,
+    F,
+}
+
+/// 16-bit registers, addressable as pairs.
+pub enum Reg16 {
+    AF,
+    BC,
+    DE,
+    HL,
+    SP,
+    PC,
+}
+
 /// Flag bits.
 #[repr(u8)]
 pub enum Flag {
@@ -17,6 +50,14 @@ pub enum Flag {
     C = 0b0001_0000,
 }
 
+/// Branch conditions for conditional jumps, calls and returns.
+pub enum Cond {
+    NZ,
+    Z,
+    NC,
+    C,
+}
+
 /// A type-pun for [u8; 2] and u16.
 ///
 /// General registers in the Gameboy CPU are structured in a way that it may be
@@ -41,6 +82,10 @@ impl U8Pair {
     fn as_u16(&mut self) -> &mut u16 {
         unsafe { &mut self.whole }
     }
+
+    fn get_u16(&self) -> u16 {
+        unsafe { self.whole }
+    }
 }
 
 #[cfg(target_endian = "little")]
@@ -52,6 +97,14 @@ impl U8Pair {
     fn as_lo(&mut self) -> &mut u8 {
         unsafe { &mut self.pair[0] }
     }
+
+    fn get_hi(&self) -> u8 {
+        unsafe { self.pair[1] }
+    }
+
+    fn get_lo(&self) -> u8 {
+        unsafe { self.pair[0] }
+    }
 }
 
 #[cfg(target_endian = "big")]
@@ -63,6 +116,14 @@ impl U8Pair {
     fn as_lo(&mut self) -> &mut u8 {
         unsafe { &mut self.pair[1] }
     }
+
+    fn get_hi(&self) -> u8 {
+        unsafe { self.pair[0] }
+    }
+
+    fn get_lo(&self) -> u8 {
+        unsafe { self.pair[1] }
+    }
 }
 
 impl Registers {
@@ -125,6 +186,58 @@ impl Registers {
         self.hl.as_u16()
     }
 
+    /// Get the value of an 8-bit register, addressed dynamically.
+    pub fn get8(&self, reg: Reg8) -> u8 {
+        match reg {
+            Reg8::A => self.af.get_hi(),
+            Reg8::B => self.bc.get_hi(),
+            Reg8::C => self.bc.get_lo(),
+            Reg8::D => self.de.get_hi(),
+            Reg8::E => self.de.get_lo(),
+            Reg8::H => self.hl.get_hi(),
+            Reg8::L => self.hl.get_lo(),
+            Reg8::F => self.af.get_lo(),
+        }
+    }
+
+    /// Set the value of an 8-bit register, addressed dynamically.
+    pub fn set8(&mut self, reg: Reg8, value: u8) {
+        match reg {
+            Reg8::A => *self.af.as_hi() = value,
+            Reg8::B => *self.bc.as_hi() = value,
+            Reg8::C => *self.bc.as_lo() = value,
+            Reg8::D => *self.de.as_hi() = value,
+            Reg8::E => *self.de.as_lo() = value,
+            Reg8::H => *self.hl.as_hi() = value,
+            Reg8::L => *self.hl.as_lo() = value,
+            Reg8::F => *self.af.as_lo() = value & 0xF0,
+        }
+    }
+
+    /// Get the value of a 16-bit register, addressed dynamically.
+    pub fn get16(&self, reg: Reg16) -> u16 {
+        match reg {
+            Reg16::AF => self.af.get_u16(),
+            Reg16::BC => self.bc.get_u16(),
+            Reg16::DE => self.de.get_u16(),
+            Reg16::HL => self.hl.get_u16(),
+            Reg16::SP => self.sp,
+            Reg16::PC => self.pc,
+        }
+    }
+
+    /// Set the value of a 16-bit register, addressed dynamically.
+    pub fn set16(&mut self, reg: Reg16, value: u16) {
+        match reg {
+            Reg16::AF => *self.af.as_u16() = value & 0xFFF0,
+            Reg16::BC => *self.bc.as_u16() = value,
+            Reg16::DE => *self.de.as_u16() = value,
+            Reg16::HL => *self.hl.as_u16() = value,
+            Reg16::SP => self.sp = value,
+            Reg16::PC => self.pc = value,
+        }
+    }
+
     /// Set the corresponding flag in the flag register to the given value.
     pub fn set_flag(&mut self, flag: Flag, value: bool) {
         let flag = flag as u8;
@@ -133,6 +246,47 @@ impl Registers {
         } else {
             *self.af.as_lo() &= !flag;
         }
+        *self.af.as_lo() &= 0xF0;
+    }
+
+    /// Returns the value of the flag register, with the unused low nibble
+    /// masked to zero.
+    pub fn f(&self) -> u8 {
+        self.af.get_lo() & 0xF0
+    }
+
+    /// Returns the `af` register pair, with the unused low nibble of `f`
+    /// masked to zero.
+    pub fn af(&self) -> u16 {
+        self.af.get_u16() & 0xFFF0
+    }
+
+    /// Set the `af` register pair, forcing the unused low nibble of `f` to
+    /// zero.
+    pub fn set_af(&mut self, value: u16) {
+        *self.af.as_u16() = value & 0xFFF0;
+    }
+
+    /// Capture the full state of the registers for a save state.
+    pub fn snapshot(&self) -> RegisterState {
+        RegisterState {
+            af: self.af.get_u16(),
+            bc: self.bc.get_u16(),
+            de: self.de.get_u16(),
+            hl: self.hl.get_u16(),
+            sp: self.sp,
+            pc: self.pc,
+        }
+    }
+
+    /// Restore the registers from a previously captured snapshot.
+    pub fn restore(&mut self, state: &RegisterState) {
+        self.af = U8Pair::from_u16(state.af & 0xFFF0);
+        self.bc = U8Pair::from_u16(state.bc);
+        self.de = U8Pair::from_u16(state.de);
+        self.hl = U8Pair::from_u16(state.hl);
+        self.sp = state.sp;
+        self.pc = state.pc;
     }
 
     /// Get the corresponding flag from the flag register.
@@ -140,4 +294,48 @@ impl Registers {
         let flag = flag as u8;
         *self.af.as_lo() & flag == flag
     }
+
+    /// Evaluate a branch condition against the current flags.
+    pub fn check(&self, cond: Cond) -> bool {
+        let f = self.af.get_lo();
+        match cond {
+            Cond::NZ => f & Flag::Z as u8 == 0,
+            Cond::Z => f & Flag::Z as u8 != 0,
+            Cond::NC => f & Flag::C as u8 == 0,
+            Cond::C => f & Flag::C as u8 != 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_restore_round_trip() {
+        let mut regs = Registers {
+            af: U8Pair::from_u16(0x1230),
+            bc: U8Pair::from_u16(0x4567),
+            de: U8Pair::from_u16(0x89ab),
+            hl: U8Pair::from_u16(0xcdef),
+            sp: 0xfffe,
+            pc: 0x0100,
+        };
+        let state = regs.snapshot();
+
+        regs.set_af(0);
+        *regs.bc() = 0;
+        *regs.de() = 0;
+        *regs.hl() = 0;
+        regs.sp = 0;
+        regs.pc = 0;
+
+        regs.restore(&state);
+        assert_eq!(regs.af(), 0x1230);
+        assert_eq!(*regs.bc(), 0x4567);
+        assert_eq!(*regs.de(), 0x89ab);
+        assert_eq!(*regs.hl(), 0xcdef);
+        assert_eq!(regs.sp, 0xfffe);
+        assert_eq!(regs.pc, 0x0100);
+    }
 }
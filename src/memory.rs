@@ -6,6 +6,17 @@ pub struct Wram {
     selected_bank: usize,
 }
 
+/// A snapshot of the full state of a `Wram`, suitable for save states.
+///
+/// `ram` is stored flattened (all eight banks concatenated) rather than as
+/// `[[u8; 0x1000]; 8]` because `serde`'s derive only has built-in impls for
+/// small fixed-size arrays, not 4KiB ones.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WramState {
+    ram: Vec<u8>,
+    selected_bank: usize,
+}
+
 impl Wram {
     /// Create a new working ram.
     pub fn new() -> Self {
@@ -16,10 +27,12 @@ impl Wram {
     }
 
     /// Read a byte from working ram.
-    /// `addr` should be between 0xc000 (inclusive) and 0xe000 (exclusive),
-    /// e.g. valid address into the wram in gameboy memory map.
+    /// `addr` should be between 0xc000 (inclusive) and 0xfe00 (exclusive),
+    /// e.g. valid address into the wram in gameboy memory map, including
+    /// the 0xe000-0xfdff echo region, which mirrors 0xc000-0xddff.
     pub fn rb(&self, addr: u16) -> u8 {
-        debug_assert!(0xc000 <= addr && addr < 0xe000);
+        debug_assert!(0xc000 <= addr && addr < 0xfe00);
+        let addr = if addr < 0xe000 { addr } else { addr - 0x2000 };
         let addr = usize::from(addr);
         if addr < 0xd000 {
             self.ram[0][addr - 0xc000]
@@ -31,7 +44,8 @@ impl Wram {
     /// Write a byte into working ram. `addr` has the same restriction as the
     /// `rb` method.
     pub fn wb(&mut self, addr: u16, value: u8) {
-        debug_assert!(0xc000 <= addr && addr < 0xe000);
+        debug_assert!(0xc000 <= addr && addr < 0xfe00);
+        let addr = if addr < 0xe000 { addr } else { addr - 0x2000 };
         let addr = usize::from(addr);
         if addr < 0xd000 {
             self.ram[0][addr - 0xc000] = value;
@@ -40,6 +54,26 @@ impl Wram {
         }
     }
 
+    /// Read a little-endian 16-bit value from working ram, as two calls to
+    /// `rb` at `addr` and `addr + 1`. `addr` has the same restriction as the
+    /// `rb` method, and `addr + 1` must also be a valid address, which
+    /// correctly handles the case where the low byte is the last byte of a
+    /// bank and the high byte lands in the next one.
+    pub fn rw(&self, addr: u16) -> u16 {
+        let lo = self.rb(addr);
+        let hi = self.rb(addr + 1);
+        u16::from_le_bytes([lo, hi])
+    }
+
+    /// Write a little-endian 16-bit value into working ram, as two calls to
+    /// `wb` at `addr` and `addr + 1`. `addr` has the same restriction as the
+    /// `rw` method.
+    pub fn ww(&mut self, addr: u16, value: u16) {
+        let [lo, hi] = value.to_le_bytes();
+        self.wb(addr, lo);
+        self.wb(addr + 1, hi);
+    }
+
     /// Read the SVBK register, assuming we're in CGB mode.
     ///
     /// SVBK register indicates which RAM bank is selected; This info is
@@ -66,4 +100,85 @@ impl Wram {
             self.selected_bank = 1;
         }
     }
+
+    /// Capture the full state of the working ram for a save state.
+    pub fn snapshot(&self) -> WramState {
+        WramState {
+            ram: self.ram.iter().flatten().copied().collect(),
+            selected_bank: self.selected_bank,
+        }
+    }
+
+    /// Restore the working ram from a previously captured snapshot.
+    ///
+    /// `state` may come from an external save state file, so its contents
+    /// are validated rather than trusted: `ram` must be exactly 8 banks'
+    /// worth of bytes, and `selected_bank` is clamped the same way
+    /// `write_svbk` clamps an SVBK write.
+    pub fn restore(&mut self, state: &WramState) {
+        assert_eq!(
+            state.ram.len(),
+            8 * 0x1000,
+            "WramState.ram has the wrong length"
+        );
+        for (bank, chunk) in self.ram.iter_mut().zip(state.ram.chunks_exact(0x1000)) {
+            bank.copy_from_slice(chunk);
+        }
+        self.selected_bank = state.selected_bank & 0b111;
+        if self.selected_bank == 0 {
+            self.selected_bank = 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn echo_ram_mirrors_bank_0_at_e000() {
+        let mut wram = Wram::new();
+        wram.wb(0xc000, 0x12);
+        assert_eq!(wram.rb(0xe000), 0x12);
+        wram.wb(0xe001, 0x34);
+        assert_eq!(wram.rb(0xc001), 0x34);
+    }
+
+    #[test]
+    fn echo_ram_mirrors_selected_bank_at_f000() {
+        let mut wram = Wram::new();
+        wram.write_svbk(3);
+        wram.wb(0xd000, 0x56);
+        assert_eq!(wram.rb(0xf000), 0x56);
+        wram.wb(0xfdff, 0x78);
+        assert_eq!(wram.rb(0xddff), 0x78);
+    }
+
+    #[test]
+    fn rw_ww_cross_bank_0_boundary() {
+        let mut wram = Wram::new();
+        wram.write_svbk(2);
+        wram.ww(0xcfff, 0xbeef);
+        assert_eq!(wram.rb(0xcfff), 0xef);
+        assert_eq!(wram.rb(0xd000), 0xbe);
+        assert_eq!(wram.rw(0xcfff), 0xbeef);
+    }
+
+    #[test]
+    fn snapshot_restore_round_trip() {
+        let mut wram = Wram::new();
+        wram.write_svbk(5);
+        wram.wb(0xc123, 0xaa);
+        wram.wb(0xd456, 0xbb);
+        let state = wram.snapshot();
+
+        wram.write_svbk(2);
+        wram.wb(0xc123, 0x00);
+        wram.wb(0xd456, 0x00);
+
+        wram.restore(&state);
+        assert_eq!(wram.rb(0xc123), 0xaa);
+        assert_eq!(wram.rb(0xd456), 0xbb);
+        assert_eq!(wram.read_svbk() & 0b111, 5);
+    }
 }